@@ -5,7 +5,9 @@
 //! `Result` except that the ok variant carries a vector of accumulated warnings. It comes with
 //! methods for converting to a `Result` by discarding or logging the warnings or treating them as
 //! errors.
-//! 
+//!
+
+#![cfg_attr(feature = "nightly", feature(try_trait_v2, try_trait_v2_residual))]
 
 #[macro_use]
 extern crate log;
@@ -34,12 +36,30 @@ impl<T, W, E> WResult<T, W, E> {
 
     /// Returns true if this `WResult` is `WErr`
     pub fn is_err(&self) -> bool {
-        match *self {
-            WOk(_, _) => true,
+        !self.is_ok()
+    }
+
+    /// Returns true if this `WResult` is `WOk` and the value and accumulated warnings match a
+    /// predicate.
+    pub fn is_ok_and<F>(self, f: F) -> bool
+        where F: FnOnce(&T, &[W]) -> bool
+    {
+        match self {
+            WOk(t, ws) => f(&t, &ws),
             WErr(_) => false,
         }
     }
 
+    /// Returns true if this `WResult` is `WErr` and the error matches a predicate.
+    pub fn is_err_and<F>(self, f: F) -> bool
+        where F: FnOnce(&E) -> bool
+    {
+        match self {
+            WOk(_, _) => false,
+            WErr(e) => f(&e),
+        }
+    }
+
     /// Returns true if this `WResult` is `WErr` or if it is `WOk` but contains warnings.
     pub fn is_warn_or_err(&self) -> bool {
         match *self {
@@ -48,6 +68,54 @@ impl<T, W, E> WResult<T, W, E> {
         }
     }
 
+    /// Borrows the contents of this `WResult`.
+    pub fn as_ref(&self) -> WResult<&T, &W, &E> {
+        match *self {
+            WOk(ref t, ref ws) => WOk(t, ws.iter().collect()),
+            WErr(ref e) => WErr(e),
+        }
+    }
+
+    /// Mutably borrows the contents of this `WResult`.
+    pub fn as_mut(&mut self) -> WResult<&mut T, &mut W, &mut E> {
+        match *self {
+            WOk(ref mut t, ref mut ws) => WOk(t, ws.iter_mut().collect()),
+            WErr(ref mut e) => WErr(e),
+        }
+    }
+
+    /// Returns an iterator over the possibly contained `WOk` value.
+    pub fn iter(&self) -> std::option::IntoIter<&T> {
+        match *self {
+            WOk(ref t, _) => Some(t).into_iter(),
+            WErr(_) => None.into_iter(),
+        }
+    }
+
+    /// Returns a mutable iterator over the possibly contained `WOk` value.
+    pub fn iter_mut(&mut self) -> std::option::IntoIter<&mut T> {
+        match *self {
+            WOk(ref mut t, _) => Some(t).into_iter(),
+            WErr(_) => None.into_iter(),
+        }
+    }
+
+    /// Returns an iterator over the accumulated warnings, if any.
+    pub fn warnings(&self) -> std::slice::Iter<'_, W> {
+        match *self {
+            WOk(_, ref ws) => ws.iter(),
+            WErr(_) => [].iter(),
+        }
+    }
+
+    /// Returns a mutable iterator over the accumulated warnings, if any.
+    pub fn warnings_mut(&mut self) -> std::slice::IterMut<'_, W> {
+        match *self {
+            WOk(_, ref mut ws) => ws.iter_mut(),
+            WErr(_) => [].iter_mut(),
+        }
+    }
+
     /// Converts this `WResult` to an `Option` by taking the taking the `WOk` value or mapping
     /// `WErr` to `None`. Any warnings are discarded.
     pub fn ok_discard(self) -> Option<T> {
@@ -109,6 +177,54 @@ impl<T, W, E> WResult<T, W, E> {
         }
     }
 
+    /// Applies `f` to the `WOk` value, discarding any warnings, or returns `default` if this is a
+    /// `WErr`. See also `map_or_werr` and `map_or_log` for versions of this function that treat
+    /// warnings as errors or log them first.
+    pub fn map_or<U, F>(self, default: U, f: F) -> U
+        where F: FnOnce(T) -> U
+    {
+        match self {
+            WOk(t, _) => f(t),
+            WErr(_) => default,
+        }
+    }
+
+    /// Applies `f` to the `WOk` value only if it has no warnings; otherwise (including on
+    /// `WErr`) returns `default`.
+    pub fn map_or_werr<U, F>(self, default: U, f: F) -> U
+        where F: FnOnce(T) -> U
+    {
+        match self {
+            WOk(t, ws) => match ws.len() {
+                0 => f(t),
+                _ => default,
+            },
+            WErr(_) => default,
+        }
+    }
+
+    /// Applies `err_f` to the `WErr` value or `ok_f` to the `WOk` value, discarding any warnings.
+    /// See also `map_or_else_werr` and `map_or_else_log` for versions of this function that treat
+    /// warnings as errors or log them first.
+    pub fn map_or_else<U, D, F>(self, err_f: D, ok_f: F) -> U
+        where D: FnOnce(E) -> U, F: FnOnce(T) -> U
+    {
+        match self {
+            WOk(t, _) => ok_f(t),
+            WErr(e) => err_f(e),
+        }
+    }
+
+    /// If `self` is `WOk`, unwraps it discarding any warnings. Otherwise returns `T::default()`.
+    pub fn unwrap_or_default(self) -> T
+        where T: Default
+    {
+        match self {
+            WOk(t, _) => t,
+            WErr(_) => T::default(),
+        }
+    }
+
     /// If `self` is `WOk`, returns `res` with the warnings from `self` accumulated into the final
     /// result. Otherwise returns the `WErr` value of `self`.
     pub fn and<U>(self, res: WResult<U, W, E>) -> WResult<U, W, E> {
@@ -213,6 +329,88 @@ impl<T, W, E> WResult<T, W, E> {
     }
 }
 
+impl<T, W, E> WResult<T, W, E>
+    where E: fmt::Debug
+{
+    /// Unwraps this `WResult`, discarding any warnings, yielding the `WOk` value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a `WErr`, with a panic message provided by the error's `Debug`
+    /// representation.
+    pub fn unwrap(self) -> T {
+        match self {
+            WOk(t, _) => t,
+            WErr(e) => panic!("called `WResult::unwrap()` on a `WErr` value: {:?}", e),
+        }
+    }
+
+    /// Unwraps this `WResult`, discarding any warnings, yielding the `WOk` value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a `WErr`, with a panic message including `msg` and the error's
+    /// `Debug` representation.
+    pub fn expect(self, msg: &str) -> T {
+        match self {
+            WOk(t, _) => t,
+            WErr(e) => panic!("{}: {:?}", msg, e),
+        }
+    }
+}
+
+impl<T, W, E> WResult<T, W, E>
+    where T: fmt::Debug, W: fmt::Debug
+{
+    /// Unwraps this `WResult`, yielding the `WErr` value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a `WOk`, with a panic message including the value and its
+    /// warnings' `Debug` representation.
+    pub fn unwrap_err(self) -> E {
+        match self {
+            WOk(t, ws) => panic!("called `WResult::unwrap_err()` on a `WOk` value: {:?}", (t, ws)),
+            WErr(e) => e,
+        }
+    }
+
+    /// Unwraps this `WResult`, yielding the `WErr` value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a `WOk`, with a panic message including `msg` and the value and
+    /// its warnings' `Debug` representation.
+    pub fn expect_err(self, msg: &str) -> E {
+        match self {
+            WOk(t, ws) => panic!("{}: {:?}", msg, (t, ws)),
+            WErr(e) => e,
+        }
+    }
+}
+
+impl<T, W, E> WResult<T, W, E>
+    where W: fmt::Debug, E: fmt::Debug
+{
+    /// Unwraps this `WResult`, yielding the `WOk` value, but only if there are no accumulated
+    /// warnings.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a `WErr`, or if it is `WOk` but carries any warnings.
+    pub fn unwrap_werr(self) -> T {
+        match self {
+            WOk(t, ws) => {
+                if !ws.is_empty() {
+                    panic!("called `WResult::unwrap_werr()` on a `WOk` value with warnings: {:?}", ws);
+                }
+                t
+            }
+            WErr(e) => panic!("called `WResult::unwrap_werr()` on a `WErr` value: {:?}", e),
+        }
+    }
+}
+
 impl<T, E> WResult<T, E, E> {
     /// Take the error value of this `WResult`, if any. Otherwise returns the first warning, if
     /// any. This function is the same as `WResult::err` except that warnings are treated as
@@ -258,6 +456,23 @@ impl<T, E> WResult<T, E, E> {
             WErr(e) => op(e),
         }
     }
+
+    /// Applies `err_f` to the `WErr` value, or to the first warning if the `WOk` value has any
+    /// warnings. Otherwise applies `ok_f` to the `WOk` value.
+    pub fn map_or_else_werr<U, D, F>(self, err_f: D, ok_f: F) -> U
+        where D: FnOnce(E) -> U, F: FnOnce(T) -> U
+    {
+        match self {
+            WOk(t, mut ws) => {
+                ws.truncate(1);
+                match ws.pop() {
+                    Some(w) => err_f(w),
+                    None => ok_f(t),
+                }
+            },
+            WErr(e) => err_f(e),
+        }
+    }
 }
 
 impl<T, W, E> WResult<T, W, E>
@@ -320,6 +535,52 @@ impl<T, W, E> WResult<T, W, E>
             WErr(e) => op(e),
         }
     }
+
+    /// Applies `f` to the `WOk` value, logging any warnings using the `warn!` macro first, or
+    /// returns `default` if this is a `WErr`.
+    pub fn map_or_log<U, F>(self, default: U, f: F) -> U
+        where F: FnOnce(T) -> U
+    {
+        match self {
+            WOk(t, ws) => {
+                for w in ws {
+                    warn!("{}", w);
+                }
+                f(t)
+            },
+            WErr(_) => default,
+        }
+    }
+
+    /// Applies `err_f` to the `WErr` value or `ok_f` to the `WOk` value, logging any warnings
+    /// using the `warn!` macro first.
+    pub fn map_or_else_log<U, D, F>(self, err_f: D, ok_f: F) -> U
+        where D: FnOnce(E) -> U, F: FnOnce(T) -> U
+    {
+        match self {
+            WOk(t, ws) => {
+                for w in ws {
+                    warn!("{}", w);
+                }
+                ok_f(t)
+            },
+            WErr(e) => err_f(e),
+        }
+    }
+}
+
+impl<T, W, E> WResult<Option<T>, W, E> {
+    /// Transposes a `WResult` of an `Option` into an `Option` of a `WResult`.
+    ///
+    /// `WOk(None, _)` maps to `None`, discarding any warnings. `WOk(Some(t), ws)` maps to
+    /// `Some(WOk(t, ws))`. `WErr(e)` maps to `Some(WErr(e))`.
+    pub fn transpose(self) -> Option<WResult<T, W, E>> {
+        match self {
+            WOk(Some(t), ws) => Some(WOk(t, ws)),
+            WOk(None, _) => None,
+            WErr(e) => Some(WErr(e)),
+        }
+    }
 }
 
 impl<T, W, E> From<Result<T, E>> for WResult<T, W, E> {
@@ -331,6 +592,46 @@ impl<T, W, E> From<Result<T, E>> for WResult<T, W, E> {
     }
 }
 
+/// An item yielded when iterating a `WResult` by value: either the success value or one of its
+/// accumulated warnings.
+pub enum WItem<T, W> {
+    /// The success value.
+    Value(T),
+    /// One of the accumulated warnings.
+    Warning(W),
+}
+
+/// An iterator over the value and accumulated warnings of a `WResult`.
+pub struct IntoIter<T, W> {
+    value: std::option::IntoIter<T>,
+    warnings: std::vec::IntoIter<W>,
+}
+
+impl<T, W> Iterator for IntoIter<T, W> {
+    type Item = WItem<T, W>;
+
+    fn next(&mut self) -> Option<WItem<T, W>> {
+        match self.value.next() {
+            Some(t) => Some(WItem::Value(t)),
+            None => self.warnings.next().map(WItem::Warning),
+        }
+    }
+}
+
+impl<T, W, E> IntoIterator for WResult<T, W, E> {
+    type Item = WItem<T, W>;
+    type IntoIter = IntoIter<T, W>;
+
+    /// Creates an iterator that yields the `WOk` value, if any, followed by the accumulated
+    /// warnings.
+    fn into_iter(self) -> IntoIter<T, W> {
+        match self {
+            WOk(t, ws) => IntoIter { value: Some(t).into_iter(), warnings: ws.into_iter() },
+            WErr(_) => IntoIter { value: None.into_iter(), warnings: Vec::new().into_iter() },
+        }
+    }
+}
+
 impl<A, T, W, E> FromIterator<WResult<A, W, E>> for WResult<T, W, E>
     where T: FromIterator<A>
 {
@@ -371,3 +672,105 @@ impl<A, T, W, E> FromIterator<WResult<A, W, E>> for WResult<T, W, E>
     }
 }
 
+impl<T, W, E> WResult<T, W, E> {
+    /// Collects an iterator of `WResult<A, W, E2>` into a `WResult<T, W, E>`, accumulating every
+    /// error encountered instead of stopping at the first one the way the `FromIterator` impl
+    /// does. Every item is consumed. If no item is a `WErr`, the result is a `WOk` carrying the
+    /// warnings of every item; otherwise the result is a `WErr` built from all the errors, and
+    /// the accumulated warnings are discarded. See `collect_warns` for a version that keeps them.
+    pub fn collect_accumulating<A, E2, I>(iter: I) -> WResult<T, W, E>
+        where T: FromIterator<A>, E: FromIterator<E2>, I: IntoIterator<Item = WResult<A, W, E2>>
+    {
+        struct Adapter<Iter, W, E2> {
+            iter: Iter,
+            warnings: Vec<W>,
+            errors: Vec<E2>,
+        }
+
+        impl<A, W, E2, Iter: Iterator<Item=WResult<A, W, E2>>> Iterator for Adapter<Iter, W, E2> {
+            type Item = A;
+
+            fn next(&mut self) -> Option<A> {
+                loop {
+                    match self.iter.next() {
+                        Some(WOk(t, ws)) => {
+                            self.warnings.extend(ws);
+                            return Some(t);
+                        },
+                        Some(WErr(e)) => self.errors.push(e),
+                        None => return None,
+                    }
+                }
+            }
+        }
+
+        let mut adapter = Adapter { iter: iter.into_iter(), warnings: Vec::new(), errors: Vec::new() };
+        let t: T = FromIterator::from_iter(adapter.by_ref());
+
+        if adapter.errors.is_empty() {
+            WOk(t, adapter.warnings)
+        } else {
+            WErr(FromIterator::from_iter(adapter.errors))
+        }
+    }
+
+    /// Collects an iterator of `WResult<A, W, E2>`, demoting every encountered `WErr` to a
+    /// warning instead of aborting the collection. Always yields a `WOk`, with the warnings from
+    /// every item (including demoted errors) accumulated in the order they were seen.
+    pub fn collect_warns<A, E2, I>(iter: I) -> WResult<T, W, E>
+        where T: FromIterator<A>, W: From<E2>, I: IntoIterator<Item = WResult<A, W, E2>>
+    {
+        let mut warnings = Vec::new();
+        let t: T = iter.into_iter().filter_map(|item| match item {
+            WOk(t, ws) => {
+                warnings.extend(ws);
+                Some(t)
+            },
+            WErr(e) => {
+                warnings.push(W::from(e));
+                None
+            },
+        }).collect();
+        WOk(t, warnings)
+    }
+}
+
+#[cfg(feature = "nightly")]
+mod try_trait {
+    use std::convert::Infallible;
+    use std::ops::{ControlFlow, FromResidual, Residual, Try};
+    use super::WResult::{self, WOk, WErr};
+
+    impl<T, W, E> Residual<(T, Vec<W>)> for WResult<Infallible, W, E> {
+        type TryType = WResult<T, W, E>;
+    }
+
+    impl<T, W, E> Try for WResult<T, W, E> {
+        type Output = (T, Vec<W>);
+        type Residual = WResult<Infallible, W, E>;
+
+        fn from_output(output: Self::Output) -> Self {
+            let (t, ws) = output;
+            WOk(t, ws)
+        }
+
+        fn branch(self) -> ControlFlow<Self::Residual, Self::Output> {
+            match self {
+                WOk(t, ws) => ControlFlow::Continue((t, ws)),
+                WErr(e) => ControlFlow::Break(WErr(e)),
+            }
+        }
+    }
+
+    impl<T, W, E, F> FromResidual<WResult<Infallible, W, F>> for WResult<T, W, E>
+        where E: From<F>
+    {
+        fn from_residual(residual: WResult<Infallible, W, F>) -> Self {
+            match residual {
+                WErr(e) => WErr(E::from(e)),
+                WOk(_, _) => unreachable!("residual is always `WErr`"),
+            }
+        }
+    }
+}
+